@@ -1,18 +1,23 @@
 //! Agent Skills discovery and management.
 //!
 //! Skills are located in `~/.config/zed/skills/<skill-name>/` with the following structure:
-//! - `SKILL.md` - Main instructions (YAML frontmatter + Markdown body)
+//! - `SKILL.md` - Main instructions (YAML or TOML frontmatter + Markdown body)
 //! - `scripts/` - Executable scripts
 //! - `references/` - Additional documentation
 //! - `assets/` - Templates, data files, images
 
 use crate::{SkillContext, SkillsPromptTemplate, Template, Templates};
 use anyhow::{Result, anyhow};
-use collections::HashMap;
+use base64::Engine as _;
+use collections::{HashMap, HashSet};
 use futures::StreamExt;
+use gpui::{BackgroundExecutor, Task};
+use parking_lot::RwLock;
+use rayon::prelude::*;
 use serde::Deserialize;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 /// The name of the main skill file.
 pub const SKILL_FILE_NAME: &str = "SKILL.md";
@@ -63,6 +68,20 @@ impl SkillMetadata {
                 self.description.len()
             ));
         }
+        if let Some(allowed_tools) = &self.allowed_tools {
+            // An empty string is the valid "no tools permitted" state (see
+            // `Skill::allowed_tools`), not a malformed list.
+            if !allowed_tools.is_empty() {
+                for token in allowed_tools.split(' ') {
+                    if token.is_empty() {
+                        return Err(anyhow!(
+                            "allowed_tools must be a single space-delimited list with no empty entries: {:?}",
+                            allowed_tools
+                        ));
+                    }
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -111,16 +130,106 @@ impl Skill {
 
         Ok(canonical_resolved)
     }
+
+    /// Resolves a file referenced from `assets/` or `references/` into
+    /// something that can be attached to a model message: images are
+    /// returned as base64 `data:` URLs, everything else is read as UTF-8
+    /// text.
+    pub fn resolve_media(&self, relative_path: &str) -> Result<DataUrl> {
+        let path = self.resolve_path(relative_path)?;
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let image_mime = match extension.as_str() {
+            "png" => Some("image/png"),
+            "jpg" | "jpeg" => Some("image/jpeg"),
+            "webp" => Some("image/webp"),
+            "gif" => Some("image/gif"),
+            _ => None,
+        };
+
+        if let Some(mime) = image_mime {
+            let bytes = std::fs::read(&path)?;
+            let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+            return Ok(DataUrl::Image(format!("data:{mime};base64,{encoded}")));
+        }
+
+        let text = std::fs::read_to_string(&path)?;
+        Ok(DataUrl::Text(text))
+    }
+
+    /// Enumerates the files directly inside this skill's `assets/` directory,
+    /// if any, sorted by path, so a skill can declare "load all of assets/"
+    /// and have those files pulled in. Subdirectories of `assets/` are not
+    /// descended into.
+    pub fn list_assets(&self) -> Result<Vec<PathBuf>> {
+        let assets_dir = self.path.join("assets");
+        if !assets_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut assets: Vec<PathBuf> = std::fs::read_dir(&assets_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+        assets.sort();
+
+        Ok(assets)
+    }
+
+    /// Parses the experimental `allowed_tools` field into a set of tool
+    /// names. Returns `None` when the field is absent, meaning all tools
+    /// are permitted, versus `Some(<empty set>)` when it's present but
+    /// empty, meaning no tools are permitted.
+    pub fn allowed_tools(&self) -> Option<HashSet<String>> {
+        let allowed_tools = self.metadata.allowed_tools.as_ref()?;
+        Some(
+            allowed_tools
+                .split(' ')
+                .filter(|token| !token.is_empty())
+                .map(|token| token.to_string())
+                .collect(),
+        )
+    }
+
+    /// Returns whether this skill permits invoking the tool named `name`.
+    pub fn permits_tool(&self, name: &str) -> bool {
+        match self.allowed_tools() {
+            Some(allowed) => allowed.contains(name),
+            None => true,
+        }
+    }
 }
 
-/// Parses YAML frontmatter from a markdown file.
+/// The result of resolving an asset or reference file via
+/// [`Skill::resolve_media`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DataUrl {
+    /// A `data:<mime>;base64,<...>` URL for an image asset.
+    Image(String),
+    /// Decoded UTF-8 text for a non-image reference file.
+    Text(String),
+}
+
+/// Parses YAML or TOML frontmatter from a markdown file.
 /// Returns the parsed metadata and the markdown body.
 fn parse_skill_file(content: &str) -> Result<(SkillMetadata, String)> {
     let content = content.trim_start();
 
+    if content.starts_with("+++") {
+        return parse_toml_frontmatter(content);
+    }
+
     // Check if content starts with frontmatter delimiter
     if !content.starts_with("---") {
-        return Err(anyhow!("SKILL.md must start with YAML frontmatter (---)"));
+        return Err(anyhow!(
+            "SKILL.md must start with YAML (---) or TOML (+++) frontmatter"
+        ));
     }
 
     // Find the end of frontmatter
@@ -144,6 +253,328 @@ fn parse_skill_file(content: &str) -> Result<(SkillMetadata, String)> {
     Ok((metadata, body))
 }
 
+/// Parses TOML frontmatter (delimited by `+++`) from a markdown file.
+/// Stricter than the YAML path of `parse_skill_file`: the closing `+++`
+/// must be followed by a newline (or end the file) before the body begins.
+fn parse_toml_frontmatter(content: &str) -> Result<(SkillMetadata, String)> {
+    let end_marker = content[3..].find("+++");
+    let (toml_part, body) = match end_marker {
+        Some(end) => {
+            let toml_end = 3 + end;
+            let toml_str = content[3..toml_end].trim().to_string();
+            let body_start = toml_end + 3;
+            let rest = &content[body_start..];
+            if !rest.is_empty() && !rest.starts_with('\n') && !rest.starts_with("\r\n") {
+                return Err(anyhow!(
+                    "TOML frontmatter closing +++ must be followed by a newline"
+                ));
+            }
+            (toml_str, rest.trim_start().to_string())
+        }
+        None => return Err(anyhow!("TOML frontmatter not properly closed with +++")),
+    };
+
+    let metadata: SkillMetadata = toml::from_str(&toml_part)
+        .map_err(|e| anyhow!("failed to parse TOML frontmatter: {}", e))?;
+
+    metadata.validate()?;
+
+    Ok((metadata, body))
+}
+
+/// Magic bytes identifying a packed `.skill` bundle.
+const SKILL_BUNDLE_MAGIC: &[u8; 5] = b"ZSKL1";
+
+/// The kind of filesystem entry stored in a `.skill` bundle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BundleEntryType {
+    Dir = 0,
+    File = 1,
+    ExecutableScript = 2,
+}
+
+impl BundleEntryType {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Dir),
+            1 => Ok(Self::File),
+            2 => Ok(Self::ExecutableScript),
+            other => Err(anyhow!("unknown skill bundle entry type: {}", other)),
+        }
+    }
+}
+
+struct BundleEntry {
+    relative_path: PathBuf,
+    entry_type: BundleEntryType,
+    content: Vec<u8>,
+}
+
+/// Packs a skill directory (`SKILL.md` plus `scripts/`, `references/`, and
+/// `assets/`) into a single-file bundle that can be shared or versioned and
+/// later restored with [`install_skill`].
+pub fn pack_skill(skill_dir: &Path) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    out.extend_from_slice(SKILL_BUNDLE_MAGIC);
+
+    write_bundle_file(&mut out, skill_dir, &skill_dir.join(SKILL_FILE_NAME))?;
+
+    for sub_dir in ["scripts", "references", "assets"] {
+        let dir = skill_dir.join(sub_dir);
+        if dir.is_dir() {
+            pack_dir_into(&mut out, skill_dir, &dir)?;
+        }
+    }
+
+    Ok(out)
+}
+
+fn pack_dir_into(out: &mut Vec<u8>, skill_dir: &Path, dir: &Path) -> Result<()> {
+    write_bundle_header(
+        out,
+        dir.strip_prefix(skill_dir).unwrap_or(dir),
+        BundleEntryType::Dir,
+        0,
+    )?;
+
+    let mut children: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+
+    for child in children {
+        if child.is_dir() {
+            pack_dir_into(out, skill_dir, &child)?;
+        } else {
+            write_bundle_file(out, skill_dir, &child)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_bundle_file(out: &mut Vec<u8>, skill_dir: &Path, path: &Path) -> Result<()> {
+    let relative_path = path.strip_prefix(skill_dir).unwrap_or(path);
+    let content = std::fs::read(path)?;
+    let entry_type = if is_executable_script(relative_path, path) {
+        BundleEntryType::ExecutableScript
+    } else {
+        BundleEntryType::File
+    };
+
+    write_bundle_header(out, relative_path, entry_type, content.len() as u64)?;
+    out.extend_from_slice(&content);
+    Ok(())
+}
+
+fn write_bundle_header(
+    out: &mut Vec<u8>,
+    relative_path: &Path,
+    entry_type: BundleEntryType,
+    content_len: u64,
+) -> Result<()> {
+    let relative_path = relative_path
+        .to_str()
+        .ok_or_else(|| anyhow!("non-utf8 path in skill bundle: {:?}", relative_path))?
+        .replace('\\', "/");
+
+    out.push(entry_type as u8);
+    out.extend_from_slice(&(relative_path.len() as u32).to_le_bytes());
+    out.extend_from_slice(relative_path.as_bytes());
+    out.extend_from_slice(&content_len.to_le_bytes());
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable_script(relative_path: &Path, path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    relative_path.starts_with("scripts")
+        && std::fs::metadata(path)
+            .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable_script(relative_path: &Path, _path: &Path) -> bool {
+    relative_path.starts_with("scripts")
+}
+
+/// Reads the raw entries out of a packed bundle without touching the
+/// filesystem.
+fn read_bundle_entries(bytes: &[u8]) -> Result<Vec<BundleEntry>> {
+    if bytes.len() < SKILL_BUNDLE_MAGIC.len()
+        || &bytes[..SKILL_BUNDLE_MAGIC.len()] != SKILL_BUNDLE_MAGIC
+    {
+        return Err(anyhow!("not a valid skill bundle"));
+    }
+
+    let mut entries = Vec::new();
+    let mut cursor = SKILL_BUNDLE_MAGIC.len();
+
+    while cursor < bytes.len() {
+        let entry_type = BundleEntryType::from_u8(
+            *bytes
+                .get(cursor)
+                .ok_or_else(|| anyhow!("truncated skill bundle"))?,
+        )?;
+        cursor += 1;
+
+        let path_len = u32::from_le_bytes(
+            bytes
+                .get(cursor..cursor + 4)
+                .ok_or_else(|| anyhow!("truncated skill bundle"))?
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        cursor += 4;
+
+        let path_bytes = bytes
+            .get(cursor..cursor + path_len)
+            .ok_or_else(|| anyhow!("truncated skill bundle"))?;
+        let relative_path = PathBuf::from(
+            std::str::from_utf8(path_bytes).map_err(|_| anyhow!("invalid path in skill bundle"))?,
+        );
+        cursor += path_len;
+
+        let content_len = u64::from_le_bytes(
+            bytes
+                .get(cursor..cursor + 8)
+                .ok_or_else(|| anyhow!("truncated skill bundle"))?
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        cursor += 8;
+
+        let content = bytes
+            .get(cursor..cursor + content_len)
+            .ok_or_else(|| anyhow!("truncated skill bundle"))?
+            .to_vec();
+        cursor += content_len;
+
+        entries.push(BundleEntry {
+            relative_path,
+            entry_type,
+            content,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Extracts a bundle produced by [`pack_skill`] into `dest_dir`. Every entry
+/// whose path contains a `..`, `.`, root, or prefix component is rejected, so
+/// a bundle entry cannot name a path outside the destination directory. The
+/// check looks at the entry's path only; unlike [`Skill::resolve_path`], it
+/// does not canonicalize the target, so symlinks already present under
+/// `dest_dir` are followed. Preserves the executable bit on files under
+/// `scripts/`.
+pub fn install_skill(bytes: &[u8], dest_dir: &Path) -> Result<()> {
+    let entries = read_bundle_entries(bytes)?;
+
+    std::fs::create_dir_all(dest_dir)?;
+
+    for entry in entries {
+        // Every component must be a plain path segment: reject `..`, `.`,
+        // and absolute paths (`RootDir`/`Prefix`) before touching the
+        // filesystem. `dest_dir.join(relative_path)` would otherwise ignore
+        // `dest_dir` entirely when `relative_path` is absolute.
+        if !entry
+            .relative_path
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)))
+        {
+            return Err(anyhow!(
+                "path traversal not allowed: {:?}",
+                entry.relative_path
+            ));
+        }
+
+        let target = dest_dir.join(&entry.relative_path);
+
+        match entry.entry_type {
+            BundleEntryType::Dir => {
+                std::fs::create_dir_all(&target)?;
+            }
+            BundleEntryType::File | BundleEntryType::ExecutableScript => {
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&target, &entry.content)?;
+
+                #[cfg(unix)]
+                if entry.entry_type == BundleEntryType::ExecutableScript {
+                    use std::os::unix::fs::PermissionsExt;
+                    let mut permissions = std::fs::metadata(&target)?.permissions();
+                    permissions.set_mode(permissions.mode() | 0o111);
+                    std::fs::set_permissions(&target, permissions)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a bundle's metadata and markdown body entirely in memory, without
+/// touching the filesystem, so a packed skill can be validated before
+/// [`install_skill`].
+pub fn discover_skills_from_bundle(bytes: &[u8]) -> Result<(SkillMetadata, String)> {
+    let entries = read_bundle_entries(bytes)?;
+    let skill_md = entries
+        .iter()
+        .find(|entry| entry.relative_path == Path::new(SKILL_FILE_NAME))
+        .ok_or_else(|| anyhow!("skill bundle is missing {}", SKILL_FILE_NAME))?;
+
+    let content = std::str::from_utf8(&skill_md.content)
+        .map_err(|_| anyhow!("{} is not valid UTF-8", SKILL_FILE_NAME))?;
+
+    parse_skill_file(content)
+}
+
+/// Reads and parses a single skill directory using the async `fs::Fs`
+/// abstraction, applying the same directory-name-matches-metadata-name and
+/// warn-and-skip-on-error semantics as the rest of discovery.
+async fn load_skill_dir(fs: &dyn fs::Fs, path: &Path) -> Option<Arc<Skill>> {
+    let skill_file = path.join(SKILL_FILE_NAME);
+
+    let content = match fs.load(&skill_file).await {
+        Ok(content) => content,
+        Err(e) => {
+            log::warn!("failed to read {:?}: {}", skill_file, e);
+            return None;
+        }
+    };
+
+    let (metadata, body) = match parse_skill_file(&content) {
+        Ok(result) => result,
+        Err(e) => {
+            log::warn!("failed to parse {:?}: {}", skill_file, e);
+            return None;
+        }
+    };
+
+    // Verify the skill name matches the directory name
+    let dir_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    if metadata.name != dir_name {
+        log::warn!(
+            "skill name '{}' doesn't match directory name '{}', skipping",
+            metadata.name,
+            dir_name
+        );
+        return None;
+    }
+
+    Some(Arc::new(Skill {
+        metadata,
+        body,
+        path: path.to_path_buf(),
+    }))
+}
+
 /// Discovers all skills in the given directory (async version).
 /// Returns a map of skill name to Skill.
 pub async fn discover_skills(
@@ -172,117 +603,134 @@ pub async fn discover_skills(
             continue;
         }
 
-        let content = match fs.load(&skill_file).await {
-            Ok(content) => content,
-            Err(e) => {
-                log::warn!("failed to read {:?}: {}", skill_file, e);
-                continue;
+        if let Some(skill) = load_skill_dir(fs, &path).await {
+            skills.insert(skill.name().to_string(), skill);
+        }
+    }
+
+    Ok(skills)
+}
+
+/// Recursively discovers skills under `root`, descending into subdirectories
+/// until a directory containing a `SKILL.md` is found. Recursion stops at
+/// that point so a skill's own `scripts/`/`references/`/`assets/` are not
+/// re-scanned for nested skills.
+pub async fn discover_skills_recursive(
+    fs: &dyn fs::Fs,
+    root: &Path,
+) -> Result<HashMap<String, Arc<Skill>>> {
+    let mut skills = HashMap::default();
+    discover_skills_recursive_into(fs, root, &mut skills).await?;
+    Ok(skills)
+}
+
+fn discover_skills_recursive_into<'a>(
+    fs: &'a dyn fs::Fs,
+    dir: &'a Path,
+    skills: &'a mut HashMap<String, Arc<Skill>>,
+) -> futures::future::BoxFuture<'a, Result<()>> {
+    Box::pin(async move {
+        if !fs.is_dir(dir).await {
+            return Ok(());
+        }
+
+        if fs.is_file(&dir.join(SKILL_FILE_NAME)).await {
+            if let Some(skill) = load_skill_dir(fs, dir).await {
+                skills.insert(skill.name().to_string(), skill);
             }
-        };
+            return Ok(());
+        }
 
-        let (metadata, body) = match parse_skill_file(&content) {
-            Ok(result) => result,
-            Err(e) => {
-                log::warn!("failed to parse {:?}: {}", skill_file, e);
-                continue;
+        let mut entries = fs.read_dir(dir).await?;
+        while let Some(entry) = entries.next().await {
+            let path = entry?;
+            if fs.is_dir(&path).await {
+                discover_skills_recursive_into(fs, &path, skills).await?;
             }
-        };
+        }
 
-        // Verify the skill name matches the directory name
-        let dir_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or_default();
-        if metadata.name != dir_name {
-            log::warn!(
-                "skill name '{}' doesn't match directory name '{}', skipping",
-                metadata.name,
-                dir_name
-            );
-            continue;
+        Ok(())
+    })
+}
+
+/// Reads and parses a single skill directory, applying the same
+/// directory-name-matches-metadata-name and warn-and-skip-on-error
+/// semantics as [`discover_skills`].
+fn load_skill_dir_sync(path: &Path) -> Option<(String, Arc<Skill>)> {
+    let skill_file = path.join(SKILL_FILE_NAME);
+    if !skill_file.exists() {
+        return None;
+    }
+
+    let content = match std::fs::read_to_string(&skill_file) {
+        Ok(content) => content,
+        Err(e) => {
+            log::warn!("failed to read {:?}: {}", skill_file, e);
+            return None;
         }
+    };
 
-        let skill = Arc::new(Skill {
-            metadata,
-            body,
-            path: path.clone(),
-        });
+    let (metadata, body) = match parse_skill_file(&content) {
+        Ok(result) => result,
+        Err(e) => {
+            log::warn!("failed to parse {:?}: {}", skill_file, e);
+            return None;
+        }
+    };
 
-        skills.insert(skill.name().to_string(), skill);
+    // Verify the skill name matches the directory name
+    let dir_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    if metadata.name != dir_name {
+        log::warn!(
+            "skill name '{}' doesn't match directory name '{}', skipping",
+            metadata.name,
+            dir_name
+        );
+        return None;
     }
 
-    Ok(skills)
+    let skill = Arc::new(Skill {
+        metadata,
+        body,
+        path: path.to_path_buf(),
+    });
+
+    Some((skill.name().to_string(), skill))
 }
 
 /// Synchronous version of skill discovery for use in synchronous contexts.
 /// Returns a map of skill name to Skill.
+///
+/// Candidate directories are collected up front, then read and parsed in
+/// parallel with rayon, since each `SKILL.md` is independent blocking IO.
 pub fn discover_skills_sync(skills_dir: &Path) -> HashMap<String, Arc<Skill>> {
-    let mut skills = HashMap::default();
-
     if !skills_dir.exists() || !skills_dir.is_dir() {
-        return skills;
+        return HashMap::default();
     }
 
     let entries = match std::fs::read_dir(skills_dir) {
         Ok(entries) => entries,
         Err(e) => {
             log::warn!("failed to read skills directory: {}", e);
-            return skills;
+            return HashMap::default();
         }
     };
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        if !path.is_dir() {
-            continue;
-        }
-
-        let skill_file = path.join(SKILL_FILE_NAME);
-        if !skill_file.exists() {
-            continue;
-        }
-
-        let content = match std::fs::read_to_string(&skill_file) {
-            Ok(content) => content,
-            Err(e) => {
-                log::warn!("failed to read {:?}: {}", skill_file, e);
-                continue;
-            }
-        };
-
-        let (metadata, body) = match parse_skill_file(&content) {
-            Ok(result) => result,
-            Err(e) => {
-                log::warn!("failed to parse {:?}: {}", skill_file, e);
-                continue;
-            }
-        };
-
-        // Verify the skill name matches the directory name
-        let dir_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or_default();
-        if metadata.name != dir_name {
-            log::warn!(
-                "skill name '{}' doesn't match directory name '{}', skipping",
-                metadata.name,
-                dir_name
-            );
-            continue;
-        }
-
-        let skill = Arc::new(Skill {
-            metadata,
-            body,
-            path: path.clone(),
-        });
+    let candidate_dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
 
-        skills.insert(skill.name().to_string(), skill);
-    }
+    let parsed: Vec<(String, Arc<Skill>)> = candidate_dirs
+        .par_iter()
+        .filter_map(|path| load_skill_dir_sync(path))
+        .collect();
 
-    skills
+    parsed.into_iter().collect()
 }
 
 /// Returns the default global skills directory path (~/.config/zed/skills).
@@ -298,15 +746,23 @@ pub fn worktree_skills_dir(worktree_root: &Path) -> PathBuf {
 /// Discovers skills from both global and worktree locations.
 /// Worktree skills take precedence over global skills with the same name.
 /// Later worktrees in the slice take precedence over earlier ones.
+///
+/// Each source directory is scanned in parallel, but the per-source maps are
+/// merged back together sequentially afterwards (global, then worktrees
+/// earliest-to-latest) so the documented precedence still holds.
 pub fn discover_all_skills_sync(worktree_roots: &[PathBuf]) -> HashMap<String, Arc<Skill>> {
-    // Start with global skills
-    let mut all_skills = discover_skills_sync(&global_skills_dir());
-
-    // Merge in worktree skills (later worktrees override earlier ones)
-    for worktree in worktree_roots {
-        let worktree_skills = discover_skills_sync(&worktree_skills_dir(worktree));
-        // Worktree skills override global skills and earlier worktree skills
-        for (name, skill) in worktree_skills {
+    let mut source_dirs = Vec::with_capacity(worktree_roots.len() + 1);
+    source_dirs.push(global_skills_dir());
+    source_dirs.extend(worktree_roots.iter().map(|root| worktree_skills_dir(root)));
+
+    let source_skills: Vec<HashMap<String, Arc<Skill>>> = source_dirs
+        .par_iter()
+        .map(|dir| discover_skills_sync(dir))
+        .collect();
+
+    let mut all_skills = HashMap::default();
+    for skills in source_skills {
+        for (name, skill) in skills {
             all_skills.insert(name, skill);
         }
     }
@@ -314,6 +770,121 @@ pub fn discover_all_skills_sync(worktree_roots: &[PathBuf]) -> HashMap<String, A
     all_skills
 }
 
+/// A live-reloading view over discovered skills, backed by filesystem
+/// watches on the global and worktree skill roots. An initial recursive
+/// scan seeds the registry, then each root is watched so a create, modify,
+/// or delete under it re-parses just the affected skill directory instead
+/// of re-scanning everything.
+pub struct SkillRegistry {
+    skills: Arc<RwLock<HashMap<String, Arc<Skill>>>>,
+    _watches: Vec<Task<()>>,
+}
+
+impl SkillRegistry {
+    /// Discovers skills under the global skills directory and the given
+    /// worktree roots, then spawns a watcher for each root that keeps the
+    /// registry in sync as files change.
+    pub async fn new(
+        fs: Arc<dyn fs::Fs>,
+        worktree_roots: Vec<PathBuf>,
+        executor: BackgroundExecutor,
+    ) -> Result<Arc<Self>> {
+        let mut roots = Vec::with_capacity(worktree_roots.len() + 1);
+        roots.push(global_skills_dir());
+        roots.extend(worktree_roots.iter().map(|root| worktree_skills_dir(root)));
+
+        // Worktree-over-global precedence is preserved the same way as
+        // `discover_all_skills_sync`: scan every root, then merge the
+        // per-root maps back together in order.
+        let mut merged = HashMap::default();
+        for root in &roots {
+            for (name, skill) in discover_skills_recursive(fs.as_ref(), root).await? {
+                merged.insert(name, skill);
+            }
+        }
+
+        let skills = Arc::new(RwLock::new(merged));
+        let mut watches = Vec::with_capacity(roots.len());
+
+        for root in roots {
+            let fs = fs.clone();
+            let skills = skills.clone();
+            watches.push(executor.spawn(Self::watch_root(fs, root, skills)));
+        }
+
+        Ok(Arc::new(Self {
+            skills,
+            _watches: watches,
+        }))
+    }
+
+    async fn watch_root(
+        fs: Arc<dyn fs::Fs>,
+        root: PathBuf,
+        skills: Arc<RwLock<HashMap<String, Arc<Skill>>>>,
+    ) {
+        let (mut events, _watcher) = fs.watch(&root, Duration::from_millis(100)).await;
+
+        while let Some(changed_paths) = events.next().await {
+            for changed_path in changed_paths {
+                if let Some(skill_dir) =
+                    find_containing_skill_dir(fs.as_ref(), &root, &changed_path).await
+                {
+                    match load_skill_dir(fs.as_ref(), &skill_dir).await {
+                        Some(skill) => {
+                            skills.write().insert(skill.name().to_string(), skill);
+                        }
+                        None => log::warn!("failed to reload skill at {:?}", skill_dir),
+                    }
+                    continue;
+                }
+
+                // No `SKILL.md` exists at or above the changed path anymore:
+                // drop any previously known skill whose directory was the
+                // changed path itself or an ancestor of it.
+                skills
+                    .write()
+                    .retain(|_, skill| !changed_path.starts_with(&skill.path));
+            }
+        }
+    }
+
+    /// Returns a snapshot of the currently known skills.
+    pub fn skills(&self) -> HashMap<String, Arc<Skill>> {
+        self.skills.read().clone()
+    }
+}
+
+/// Walks up from `changed_path` toward `root` looking for the nearest
+/// ancestor directory that contains a `SKILL.md`, mirroring the stopping
+/// condition used by `discover_skills_recursive`. This is what lets a
+/// change several directories deep (e.g. `root/team-a/pdf-processing/SKILL.md`)
+/// resolve back to the real skill directory instead of `root`'s immediate
+/// child.
+async fn find_containing_skill_dir(
+    fs: &dyn fs::Fs,
+    root: &Path,
+    changed_path: &Path,
+) -> Option<PathBuf> {
+    let mut dir = if fs.is_dir(changed_path).await {
+        changed_path.to_path_buf()
+    } else {
+        changed_path.parent()?.to_path_buf()
+    };
+
+    loop {
+        if fs.is_file(&dir.join(SKILL_FILE_NAME)).await {
+            return Some(dir);
+        }
+
+        if dir == root || !dir.starts_with(root) {
+            return None;
+        }
+
+        dir = dir.parent()?.to_path_buf();
+    }
+}
+
 /// Format skills for display in the system prompt using handlebars templating.
 pub fn format_skills_for_prompt(
     skills: &HashMap<String, Arc<Skill>>,
@@ -325,13 +896,25 @@ pub fn format_skills_for_prompt(
 
     let skill_contexts: Vec<SkillContext> = skill_list
         .into_iter()
-        .map(|skill| SkillContext {
-            name: skill.name().to_string(),
-            description: if skill.description().len() > 80 {
-                format!("{}...", &skill.description()[..77])
-            } else {
-                skill.description().to_string()
-            },
+        .map(|skill| {
+            let mut description = skill.description().to_string();
+
+            if let Some(allowed_tools) = skill.allowed_tools() {
+                let mut tools: Vec<String> = allowed_tools.into_iter().collect();
+                tools.sort();
+                description = format!("{description} [tools: {}]", tools.join(", "));
+            }
+
+            // Truncate after appending the allowlist so the 80-char cap
+            // still applies to what's actually sent to the model.
+            if description.len() > 80 {
+                description = format!("{}...", &description[..77]);
+            }
+
+            SkillContext {
+                name: skill.name().to_string(),
+                description,
+            }
         })
         .collect();
 
@@ -346,6 +929,97 @@ pub fn format_skills_for_prompt(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_discover_skills_recursive_nested_skill(cx: &mut gpui::TestAppContext) {
+        let fs = fs::FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/skills"),
+            serde_json::json!({
+                "team-a": {
+                    "pdf-processing": {
+                        "SKILL.md": "---\nname: pdf-processing\ndescription: Extract text from PDFs\n---\nBody\n",
+                    }
+                }
+            }),
+        )
+        .await;
+
+        let skills = discover_skills_recursive(fs.as_ref(), Path::new(path!("/skills")))
+            .await
+            .unwrap();
+
+        assert_eq!(skills.len(), 1);
+        let skill = skills
+            .get("pdf-processing")
+            .expect("nested skill should be discovered");
+        assert_eq!(
+            skill.path,
+            PathBuf::from(path!("/skills/team-a/pdf-processing"))
+        );
+    }
+
+    #[gpui::test]
+    async fn test_skill_registry_live_reloads_nested_skill(cx: &mut gpui::TestAppContext) {
+        let fs = fs::FakeFs::new(cx.executor());
+        let worktree_root = PathBuf::from(path!("/worktree"));
+        let skills_root = worktree_skills_dir(&worktree_root);
+        let skill_dir = skills_root.join("team-a").join("pdf-processing");
+
+        fs.insert_tree(
+            &skills_root,
+            serde_json::json!({
+                "team-a": {
+                    "pdf-processing": {
+                        "SKILL.md": "---\nname: pdf-processing\ndescription: Extract text from PDFs\n---\nBody\n",
+                    }
+                }
+            }),
+        )
+        .await;
+
+        let registry = SkillRegistry::new(
+            Arc::new(fs.clone()) as Arc<dyn fs::Fs>,
+            vec![worktree_root],
+            cx.executor(),
+        )
+        .await
+        .unwrap();
+
+        assert!(registry.skills().contains_key("pdf-processing"));
+
+        // Editing the nested SKILL.md should reload just that skill.
+        fs.insert_file(
+            skill_dir.join(SKILL_FILE_NAME),
+            "---\nname: pdf-processing\ndescription: Updated description\n---\nBody\n"
+                .as_bytes()
+                .to_vec(),
+        )
+        .await;
+        cx.executor().run_until_parked();
+
+        let updated = registry
+            .skills()
+            .get("pdf-processing")
+            .cloned()
+            .expect("nested skill should still be present after edit");
+        assert_eq!(updated.description(), "Updated description");
+
+        // Deleting the nested skill directory should drop it from the registry.
+        fs.remove_dir(
+            &skill_dir,
+            fs::RemoveOptions {
+                recursive: true,
+                ignore_if_not_exists: true,
+            },
+        )
+        .await
+        .unwrap();
+        cx.executor().run_until_parked();
+
+        assert!(!registry.skills().contains_key("pdf-processing"));
+    }
 
     #[test]
     fn test_parse_skill_file_valid() {
@@ -375,6 +1049,37 @@ This skill helps you work with PDF files.
         assert!(parse_skill_file(content).is_err());
     }
 
+    #[test]
+    fn test_parse_skill_file_toml_frontmatter() {
+        let content = r#"+++
+name = "pdf-processing"
+description = "Extract text and tables from PDF files"
+license = "MIT"
++++
+# PDF Processing
+
+This skill helps you work with PDF files.
+"#;
+
+        let (metadata, body) = parse_skill_file(content).unwrap();
+        assert_eq!(metadata.name, "pdf-processing");
+        assert_eq!(
+            metadata.description,
+            "Extract text and tables from PDF files"
+        );
+        assert_eq!(metadata.license, Some("MIT".to_string()));
+        assert!(body.starts_with("# PDF Processing"));
+    }
+
+    #[test]
+    fn test_parse_skill_file_toml_frontmatter_unclosed() {
+        let content = r#"+++
+name = "pdf-processing"
+description = "Extract text and tables from PDF files"
+"#;
+        assert!(parse_skill_file(content).is_err());
+    }
+
     #[test]
     fn test_skill_metadata_validation() {
         let metadata = SkillMetadata {
@@ -408,6 +1113,56 @@ This skill helps you work with PDF files.
         assert!(metadata.validate().is_err());
     }
 
+    #[test]
+    fn test_allowed_tools_validation() {
+        let mut metadata = SkillMetadata {
+            name: "valid-skill".to_string(),
+            description: "A valid description".to_string(),
+            license: None,
+            compatibility: None,
+            metadata: HashMap::default(),
+            allowed_tools: Some("read_file edit_file".to_string()),
+        };
+        assert!(metadata.validate().is_ok());
+
+        metadata.allowed_tools = Some("read_file  edit_file".to_string());
+        assert!(metadata.validate().is_err());
+
+        // An empty string means "no tools permitted" and must validate.
+        metadata.allowed_tools = Some(String::new());
+        assert!(metadata.validate().is_ok());
+    }
+
+    #[test]
+    fn test_skill_allowed_tools_and_permits_tool() {
+        let mut skill = Skill {
+            metadata: SkillMetadata {
+                name: "test".to_string(),
+                description: "test".to_string(),
+                license: None,
+                compatibility: None,
+                metadata: HashMap::default(),
+                allowed_tools: None,
+            },
+            body: String::new(),
+            path: PathBuf::from("/home/user/.config/zed/skills/test"),
+        };
+
+        // Absent means all tools are permitted.
+        assert_eq!(skill.allowed_tools(), None);
+        assert!(skill.permits_tool("read_file"));
+
+        // Present-but-empty means no tools are permitted.
+        skill.metadata.allowed_tools = Some(String::new());
+        assert_eq!(skill.allowed_tools(), Some(HashSet::default()));
+        assert!(!skill.permits_tool("read_file"));
+
+        // A populated list scopes permission to exactly those tools.
+        skill.metadata.allowed_tools = Some("read_file edit_file".to_string());
+        assert!(skill.permits_tool("read_file"));
+        assert!(!skill.permits_tool("delete_file"));
+    }
+
     #[test]
     fn test_skill_resolve_path() {
         let skill = Skill {
@@ -428,4 +1183,114 @@ This skill helps you work with PDF files.
         assert!(skill.resolve_path("../etc/passwd").is_err());
         assert!(skill.resolve_path("scripts/../../../etc/passwd").is_err());
     }
+
+    #[test]
+    fn test_skill_resolve_media() {
+        let dir = std::env::temp_dir().join("zed-skills-test-resolve-media");
+        std::fs::create_dir_all(dir.join("references")).unwrap();
+        std::fs::write(dir.join("references/notes.txt"), "hello skill").unwrap();
+        std::fs::write(dir.join("references/pixel.png"), [0u8, 1, 2, 3]).unwrap();
+
+        let skill = Skill {
+            metadata: SkillMetadata {
+                name: "test".to_string(),
+                description: "test".to_string(),
+                license: None,
+                compatibility: None,
+                metadata: HashMap::default(),
+                allowed_tools: None,
+            },
+            body: String::new(),
+            path: dir.clone(),
+        };
+
+        match skill.resolve_media("references/notes.txt").unwrap() {
+            DataUrl::Text(text) => assert_eq!(text, "hello skill"),
+            DataUrl::Image(_) => panic!("expected text"),
+        }
+
+        match skill.resolve_media("references/pixel.png").unwrap() {
+            DataUrl::Image(url) => assert!(url.starts_with("data:image/png;base64,")),
+            DataUrl::Text(_) => panic!("expected image"),
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pack_and_install_skill_roundtrip() {
+        let src_dir = std::env::temp_dir().join("zed-skills-test-bundle-src");
+        let dest_dir = std::env::temp_dir().join("zed-skills-test-bundle-dest");
+        let _ = std::fs::remove_dir_all(&src_dir);
+        let _ = std::fs::remove_dir_all(&dest_dir);
+
+        std::fs::create_dir_all(src_dir.join("scripts")).unwrap();
+        std::fs::create_dir_all(src_dir.join("references")).unwrap();
+        std::fs::write(
+            src_dir.join(SKILL_FILE_NAME),
+            "---\nname: bundled-skill\ndescription: A bundled skill\n---\nBody text\n",
+        )
+        .unwrap();
+        std::fs::write(src_dir.join("scripts/run.sh"), "#!/bin/sh\necho hi\n").unwrap();
+        std::fs::write(src_dir.join("references/notes.md"), "some notes").unwrap();
+
+        let bundle = pack_skill(&src_dir).unwrap();
+
+        let (metadata, body) = discover_skills_from_bundle(&bundle).unwrap();
+        assert_eq!(metadata.name, "bundled-skill");
+        assert!(body.starts_with("Body text"));
+
+        install_skill(&bundle, &dest_dir).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dest_dir.join(SKILL_FILE_NAME)).unwrap(),
+            std::fs::read_to_string(src_dir.join(SKILL_FILE_NAME)).unwrap()
+        );
+        assert_eq!(
+            std::fs::read_to_string(dest_dir.join("references/notes.md")).unwrap(),
+            "some notes"
+        );
+
+        std::fs::remove_dir_all(&src_dir).unwrap();
+        std::fs::remove_dir_all(&dest_dir).unwrap();
+    }
+
+    #[test]
+    fn test_install_skill_rejects_path_traversal() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(SKILL_BUNDLE_MAGIC);
+        let relative_path = "../../etc/evil";
+        bytes.push(BundleEntryType::File as u8);
+        bytes.extend_from_slice(&(relative_path.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(relative_path.as_bytes());
+        bytes.extend_from_slice(&4u64.to_le_bytes());
+        bytes.extend_from_slice(b"evil");
+
+        let dest_dir = std::env::temp_dir().join("zed-skills-test-bundle-traversal");
+        let _ = std::fs::remove_dir_all(&dest_dir);
+
+        assert!(install_skill(&bytes, &dest_dir).is_err());
+
+        let _ = std::fs::remove_dir_all(&dest_dir);
+    }
+
+    #[test]
+    fn test_install_skill_rejects_absolute_path() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(SKILL_BUNDLE_MAGIC);
+        let relative_path = "/etc/cron.d/evil";
+        bytes.push(BundleEntryType::File as u8);
+        bytes.extend_from_slice(&(relative_path.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(relative_path.as_bytes());
+        bytes.extend_from_slice(&4u64.to_le_bytes());
+        bytes.extend_from_slice(b"evil");
+
+        let dest_dir = std::env::temp_dir().join("zed-skills-test-bundle-absolute");
+        let _ = std::fs::remove_dir_all(&dest_dir);
+
+        assert!(install_skill(&bytes, &dest_dir).is_err());
+        assert!(!Path::new("/etc/cron.d/evil").exists());
+
+        let _ = std::fs::remove_dir_all(&dest_dir);
+    }
 }